@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use crate::services::auth_service::AuthService;
+use crate::services::settings_service::SettingsService;
 use crate::types::{AuthResponse, LoginCredentials};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -7,12 +8,13 @@ pub struct ApiError {
     pub message: String,
 }
 
-/// Login command - authenticates user with FastAPI backend
+/// Login command - authenticates user with the configured backend
 #[tauri::command]
 pub async fn login(username: String, password: String) -> Result<AuthResponse, String> {
     let credentials = LoginCredentials { username, password };
-    
-    match AuthService::login(credentials).await {
+    let base_url = resolve_api_url().await?;
+
+    match AuthService::login(credentials, &base_url).await {
         Ok(response) => Ok(response),
         Err(e) => Err(format!("Login failed: {}", e)),
     }
@@ -20,8 +22,8 @@ pub async fn login(username: String, password: String) -> Result<AuthResponse, S
 
 /// Logout command - clears stored credentials
 #[tauri::command]
-pub async fn logout() -> Result<(), String> {
-    match AuthService::logout().await {
+pub async fn logout(username: String) -> Result<(), String> {
+    match AuthService::logout(&username).await {
         Ok(_) => Ok(()),
         Err(e) => Err(format!("Logout failed: {}", e)),
     }
@@ -30,8 +32,17 @@ pub async fn logout() -> Result<(), String> {
 /// Refresh token command - refreshes authentication token
 #[tauri::command]
 pub async fn refresh_token(token: String) -> Result<AuthResponse, String> {
-    match AuthService::refresh_token(token).await {
+    let base_url = resolve_api_url().await?;
+
+    match AuthService::refresh_token(token, &base_url).await {
         Ok(response) => Ok(response),
         Err(e) => Err(format!("Token refresh failed: {}", e)),
     }
 }
+
+/// Load settings and resolve the API base URL, upgrading to HTTPS when configured
+async fn resolve_api_url() -> Result<String, String> {
+    let settings = SettingsService::load_settings().await.unwrap_or_default();
+    let server_url = settings.resolve_server_url().map_err(|e| e.to_string())?;
+    Ok(format!("{}/api", server_url))
+}
@@ -1,7 +1,11 @@
+use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use tauri::Window;
+use crate::services::auth_service::AuthService;
 use crate::services::game_service::GameService;
-use crate::types::{GameVersion, DownloadProgress};
+use crate::services::repair_manager::RepairManager;
+use crate::services::settings_service::SettingsService;
+use crate::types::{GameVersion, LauncherStatus, ManifestEntry, RepairResult};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LaunchResult {
@@ -9,45 +13,72 @@ pub struct LaunchResult {
     pub message: String,
 }
 
-/// Check current game version from server
+/// Check current game version from the configured GitHub `owner/repo`
 #[tauri::command]
 pub async fn check_version() -> Result<GameVersion, String> {
-    match GameService::check_version().await {
+    let settings = SettingsService::load_settings().await.unwrap_or_default();
+
+    match GameService::check_version(&settings.github_owner, &settings.github_repo).await {
         Ok(version) => Ok(version),
         Err(e) => Err(format!("Failed to check version: {}", e)),
     }
 }
 
-/// Download game files with progress tracking
+/// Download game files from `download_url` (the release asset URL returned by
+/// `check_version`), streaming stage/progress/log updates on `launcher-status`
 #[tauri::command]
 pub async fn download_game(
     window: Window,
     version: String,
+    download_url: String,
     install_path: String,
 ) -> Result<(), String> {
-    match GameService::download_game(version, install_path, move |progress| {
-        // Emit progress to frontend
-        let _ = window.emit("download-progress", progress);
+    let error_window = window.clone();
+    match GameService::download_game(version, download_url, install_path, move |status| {
+        emit_status(&window, status);
     })
     .await
     {
         Ok(_) => Ok(()),
-        Err(e) => Err(format!("Download failed: {}", e)),
+        Err(e) => {
+            emit_status(&error_window, LauncherStatus::failed("Downloading", e.to_string()));
+            Err(format!("Download failed: {}", e))
+        }
     }
 }
 
 /// Launch the game with authentication token
 #[tauri::command]
-pub async fn launch_game(token: String, install_path: String) -> Result<LaunchResult, String> {
-    match GameService::launch_game(token, install_path).await {
+pub async fn launch_game(window: Window, token: String, install_path: String) -> Result<LaunchResult, String> {
+    let settings = SettingsService::load_settings().await.unwrap_or_default();
+    let base_url = settings.resolve_server_url().map_err(|e| e.to_string())?;
+
+    let token = AuthService::ensure_valid_token(token, &format!("{}/api", base_url))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let error_window = window.clone();
+    match GameService::launch_game(token, install_path, settings.wine_config, move |status| {
+        emit_status(&window, status);
+    })
+    .await
+    {
         Ok(_) => Ok(LaunchResult {
             success: true,
             message: "Game launched successfully".to_string(),
         }),
-        Err(e) => Err(format!("Failed to launch game: {}", e)),
+        Err(e) => {
+            emit_status(&error_window, LauncherStatus::failed("Launching", e.to_string()));
+            Err(format!("Failed to launch game: {}", e))
+        }
     }
 }
 
+/// Emit a `LauncherStatus` update on the single channel the frontend listens on
+fn emit_status(window: &Window, status: LauncherStatus) {
+    let _ = window.emit("launcher-status", status);
+}
+
 /// Get the default installation path for the game
 #[tauri::command]
 pub async fn get_install_path() -> Result<String, String> {
@@ -56,3 +87,45 @@ pub async fn get_install_path() -> Result<String, String> {
         Err(e) => Err(format!("Failed to get install path: {}", e)),
     }
 }
+
+/// Verify an installation against a manifest without repairing anything
+#[tauri::command]
+pub async fn verify_installation(
+    install_path: String,
+    manifest: Vec<ManifestEntry>,
+) -> Result<RepairResult, String> {
+    tokio::task::spawn_blocking(move || {
+        RepairManager::verify_installation(&PathBuf::from(install_path), &manifest)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Verify an installation and re-download any missing, wrong-size, or corrupt files
+#[tauri::command]
+pub async fn repair_installation(
+    window: Window,
+    install_path: String,
+    manifest: Vec<ManifestEntry>,
+) -> Result<RepairResult, String> {
+    let base_url = SettingsService::load_settings()
+        .await
+        .map_err(|e| e.to_string())?
+        .resolve_server_url()
+        .map_err(|e| e.to_string())?;
+
+    let error_window = window.clone();
+    RepairManager::repair_installation(
+        &PathBuf::from(install_path),
+        &manifest,
+        &format!("{}/api/game/file", base_url),
+        move |progress| {
+            emit_status(&window, LauncherStatus::from_download("Verifying", &progress));
+        },
+    )
+    .await
+    .map_err(|e| {
+        emit_status(&error_window, LauncherStatus::failed("Verifying", e.to_string()));
+        format!("Repair failed: {}", e)
+    })
+}
@@ -1,5 +1,7 @@
+use std::error::Error;
 use serde::{Deserialize, Serialize};
 use crate::services::settings_service::SettingsService;
+use crate::types::WineConfig;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LauncherSettings {
@@ -8,6 +10,23 @@ pub struct LauncherSettings {
     pub auto_update: bool,
     pub remember_me: bool,
     pub language: String,
+    #[serde(default)]
+    pub wine_config: WineConfig,
+    #[serde(default)]
+    pub use_https: bool,
+    /// GitHub `owner/repo` that `check_version`/`download_game` fetch releases from
+    #[serde(default = "default_github_owner")]
+    pub github_owner: String,
+    #[serde(default = "default_github_repo")]
+    pub github_repo: String,
+}
+
+fn default_github_owner() -> String {
+    "anatoli308".to_string()
+}
+
+fn default_github_repo() -> String {
+    "gamelauncher".to_string()
 }
 
 impl Default for LauncherSettings {
@@ -18,6 +37,28 @@ impl Default for LauncherSettings {
             auto_update: true,
             remember_me: false,
             language: "en".to_string(),
+            wine_config: WineConfig::default(),
+            use_https: false,
+            github_owner: default_github_owner(),
+            github_repo: default_github_repo(),
+        }
+    }
+}
+
+impl LauncherSettings {
+    /// Resolve the configured server URL, upgrading it to HTTPS when `use_https` is
+    /// enabled. Refuses to hand back a plaintext URL once HTTPS is required.
+    pub fn resolve_server_url(&self) -> Result<String, Box<dyn Error>> {
+        if !self.use_https {
+            return Ok(self.server_url.clone());
+        }
+
+        if self.server_url.starts_with("https://") {
+            Ok(self.server_url.clone())
+        } else if let Some(rest) = self.server_url.strip_prefix("http://") {
+            Ok(format!("https://{}", rest))
+        } else {
+            Err(format!("Cannot enforce HTTPS on server URL: {}", self.server_url).into())
         }
     }
 }
@@ -17,6 +17,8 @@ fn main() {
             game::download_game,
             game::launch_game,
             game::get_install_path,
+            game::verify_installation,
+            game::repair_installation,
             settings::get_settings,
             settings::save_settings,
         ])
@@ -1,19 +1,36 @@
+use base64::Engine;
+use keyring::Entry;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use crate::types::{AuthResponse, LoginCredentials};
 use std::error::Error;
 
+/// Keyring service name tokens are stored under, keyed by username
+const KEYRING_SERVICE: &str = "gamelauncher";
+/// Refresh the token if fewer than this many seconds remain before it expires
+const REFRESH_WINDOW_SECS: i64 = 300;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredSession {
+    token: String,
+    expires_at: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtClaims {
+    exp: Option<i64>,
+}
+
 pub struct AuthService;
 
 impl AuthService {
-    const API_URL: &'static str = "http://localhost:8000/api";
-
-    /// Authenticate user with FastAPI backend
-    pub async fn login(credentials: LoginCredentials) -> Result<AuthResponse, Box<dyn Error>> {
+    /// Authenticate user against `base_url` (the launcher's configured server)
+    pub async fn login(credentials: LoginCredentials, base_url: &str) -> Result<AuthResponse, Box<dyn Error>> {
         let client = Client::new();
-        
+
         let response = client
-            .post(format!("{}/loginUser", Self::API_URL))
+            .post(format!("{}/loginUser", base_url))
             .json(&json!({
                 "username": credentials.username,
                 "password": credentials.password
@@ -23,10 +40,9 @@ impl AuthService {
 
         if response.status().is_success() {
             let auth_response: AuthResponse = response.json().await?;
-            
-            // Store token securely (in production, use OS keychain)
-            Self::store_token(&auth_response.token)?;
-            
+
+            Self::store_token(&auth_response.username, &auth_response.token)?;
+
             Ok(auth_response)
         } else {
             let status = response.status();
@@ -36,60 +52,89 @@ impl AuthService {
     }
 
     /// Logout user and clear stored credentials
-    pub async fn logout() -> Result<(), Box<dyn Error>> {
-        Self::clear_token()?;
+    pub async fn logout(username: &str) -> Result<(), Box<dyn Error>> {
+        Self::clear_token(username)?;
         Ok(())
     }
 
-    /// Refresh authentication token
-    pub async fn refresh_token(token: String) -> Result<AuthResponse, Box<dyn Error>> {
+    /// Refresh authentication token against `base_url`
+    pub async fn refresh_token(token: String, base_url: &str) -> Result<AuthResponse, Box<dyn Error>> {
         let client = Client::new();
-        
+
         let response = client
-            .post(format!("{}/refreshToken", Self::API_URL))
+            .post(format!("{}/refreshToken", base_url))
             .header("Authorization", format!("Bearer {}", token))
             .send()
             .await?;
 
         if response.status().is_success() {
             let auth_response: AuthResponse = response.json().await?;
-            Self::store_token(&auth_response.token)?;
+            Self::store_token(&auth_response.username, &auth_response.token)?;
             Ok(auth_response)
         } else {
             Err("Token refresh failed".into())
         }
     }
 
-    /// Store token securely
-    fn store_token(token: &str) -> Result<(), Box<dyn Error>> {
-        // In production: use keyring crate for OS keychain
-        // For now, store in app data directory
-        let app_dir = dirs::data_local_dir()
-            .ok_or("Failed to get app data directory")?
-            .join("RemakeSoF");
-        
-        std::fs::create_dir_all(&app_dir)?;
-        std::fs::write(app_dir.join(".token"), token)?;
+    /// Ensure `token` is valid for at least the refresh window, transparently
+    /// refreshing and persisting a new token if it's expired or about to expire.
+    /// Tokens without a decodable `exp` claim are assumed not to expire.
+    pub async fn ensure_valid_token(token: String, base_url: &str) -> Result<String, Box<dyn Error>> {
+        match Self::decode_expiry(&token) {
+            Some(expires_at) if Self::seconds_until(expires_at) < REFRESH_WINDOW_SECS => {
+                let refreshed = Self::refresh_token(token, base_url)
+                    .await
+                    .map_err(|_| "Session expired, please log in again")?;
+                Ok(refreshed.token)
+            }
+            _ => Ok(token),
+        }
+    }
+
+    /// Decode the `exp` claim from a JWT without verifying its signature
+    fn decode_expiry(token: &str) -> Option<i64> {
+        let payload = token.split('.').nth(1)?;
+        let decoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(payload)
+            .ok()?;
+        let claims: JwtClaims = serde_json::from_slice(&decoded).ok()?;
+        claims.exp
+    }
+
+    fn seconds_until(expires_at: i64) -> i64 {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        expires_at - now
+    }
+
+    /// Store the token in the OS secret store (Credential Manager / Keychain / libsecret)
+    fn store_token(username: &str, token: &str) -> Result<(), Box<dyn Error>> {
+        let session = StoredSession {
+            token: token.to_string(),
+            expires_at: Self::decode_expiry(token),
+        };
+
+        let entry = Entry::new(KEYRING_SERVICE, username)?;
+        entry.set_password(&serde_json::to_string(&session)?)?;
         Ok(())
     }
 
-    /// Clear stored token
-    fn clear_token() -> Result<(), Box<dyn Error>> {
-        let app_dir = dirs::data_local_dir()
-            .ok_or("Failed to get app data directory")?
-            .join("RemakeSoF");
-        
-        let token_path = app_dir.join(".token");
-        if token_path.exists() {
-            std::fs::remove_file(token_path)?;
+    /// Clear the stored token for `username`
+    fn clear_token(username: &str) -> Result<(), Box<dyn Error>> {
+        let entry = Entry::new(KEYRING_SERVICE, username)?;
+        match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.into()),
         }
-        Ok(())
     }
 
-    /// Retrieve stored token
-    pub fn get_stored_token() -> Option<String> {
-        let app_dir = dirs::data_local_dir()?.join("RemakeSoF");
-        let token = std::fs::read_to_string(app_dir.join(".token")).ok()?;
-        Some(token)
+    /// Retrieve the stored token for `username`, if any
+    pub fn get_stored_token(username: &str) -> Option<String> {
+        let entry = Entry::new(KEYRING_SERVICE, username).ok()?;
+        let raw = entry.get_password().ok()?;
+        let session: StoredSession = serde_json::from_str(&raw).ok()?;
+        Some(session.token)
     }
 }
@@ -0,0 +1,79 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use crate::types::WineConfig;
+
+/// Builds and runs the game through Wine/Proton on non-Windows hosts
+pub struct CompatibilityRunner;
+
+impl CompatibilityRunner {
+    /// Ensure the configured Wine prefix exists, creating and initializing it if needed
+    pub fn ensure_prefix<F>(config: &WineConfig, progress_callback: &F) -> Result<(), Box<dyn Error>>
+    where
+        F: Fn(&str),
+    {
+        let prefix = PathBuf::from(&config.prefix_path);
+
+        if !prefix.join("drive_c").exists() {
+            progress_callback("Creating Wine prefix...");
+            std::fs::create_dir_all(&prefix)?;
+
+            let status = Command::new(&config.wine_binary)
+                .arg("wineboot")
+                .arg("--init")
+                .env("WINEPREFIX", &prefix)
+                .status()?;
+
+            if !status.success() {
+                return Err("Failed to initialize Wine prefix".into());
+            }
+        }
+
+        // Installed once per prefix: without this sentinel, a relaunch would re-run
+        // the DXVK installer (and its latency) on every single launch.
+        let dxvk_marker = prefix.join(".dxvk_installed");
+        if config.dxvk_enabled && !dxvk_marker.exists() {
+            progress_callback("Installing DXVK...");
+            Self::install_dxvk(config)?;
+            std::fs::write(&dxvk_marker, "")?;
+        }
+
+        Ok(())
+    }
+
+    /// Install DXVK into the prefix via the configured tool (a path, or a name resolved on PATH)
+    fn install_dxvk(config: &WineConfig) -> Result<(), Box<dyn Error>> {
+        let status = Command::new(&config.dxvk_tool)
+            .arg("install")
+            .env("WINEPREFIX", &config.prefix_path)
+            .env("WINE", &config.wine_binary)
+            .status()?;
+
+        if !status.success() {
+            return Err("DXVK setup failed".into());
+        }
+
+        Ok(())
+    }
+
+    /// Launch `game_exe` through the configured Wine prefix, passing through extra args
+    pub fn launch(
+        config: &WineConfig,
+        game_exe: &Path,
+        args: &[String],
+    ) -> Result<std::process::Child, Box<dyn Error>> {
+        let mut command = Command::new(&config.wine_binary);
+        command
+            .arg(game_exe)
+            .args(args)
+            .env("WINEPREFIX", &config.prefix_path)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        for (key, value) in &config.extra_env {
+            command.env(key, value);
+        }
+
+        Ok(command.spawn()?)
+    }
+}
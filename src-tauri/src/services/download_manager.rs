@@ -1,102 +1,353 @@
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use futures_util::StreamExt;
 use std::error::Error;
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use crate::types::DownloadProgress;
 
+/// Default number of concurrent Range requests used to split a download
+pub const DEFAULT_SEGMENT_COUNT: usize = 4;
+/// How often the speed/ETA sampler wakes up
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+/// Smoothing factor for the exponential moving average of download speed
+const SPEED_EMA_ALPHA: f32 = 0.3;
+
+#[derive(Clone)]
+struct Segment {
+    /// Inclusive byte range within the destination file
+    start: u64,
+    end: u64,
+    part_path: PathBuf,
+}
+
 pub struct DownloadManager;
 
 impl DownloadManager {
-    /// Download file with progress tracking
+    /// Download a file, splitting it into concurrent Range requests when the server
+    /// supports it, and reporting aggregate progress with smoothed speed and ETA.
+    ///
+    /// Falls back to a single stream when the server doesn't honor `Range`. Each
+    /// segment is written to its own `.partN` file so an interrupted download can be
+    /// resumed by calling this again with the same destination. Replaces the old
+    /// `download_file`/`resume_download` split with one resumable engine.
     pub async fn download_file<F>(
         url: &str,
         destination: &Path,
+        segment_count: usize,
         progress_callback: F,
     ) -> Result<(), Box<dyn Error>>
     where
-        F: Fn(DownloadProgress) + Send + 'static,
+        F: Fn(DownloadProgress) + Send + Sync + 'static,
     {
         let client = Client::new();
-        let response = client.get(url).send().await?;
+        let total_size = Self::fetch_total_size(&client, url).await?;
+        let supports_range = Self::supports_range(&client, url).await?;
 
-        let total_size = response.content_length().unwrap_or(0);
-        let mut downloaded: u64 = 0;
-        let mut stream = response.bytes_stream();
-        let mut file = File::create(destination)?;
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
 
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
-            file.write_all(&chunk)?;
-            downloaded += chunk.len() as u64;
+        // Seeded at 0: `download_segmented`/`download_single` each account for their own
+        // already-downloaded bytes as they run, so seeding this from existing part files
+        // too would double-count them.
+        let downloaded = Arc::new(AtomicU64::new(0));
+        let progress_callback = Arc::new(progress_callback);
+
+        let sampler = Self::spawn_sampler(
+            Arc::clone(&downloaded),
+            total_size,
+            Arc::clone(&progress_callback),
+        );
+
+        let result = if supports_range && total_size > 0 {
+            Self::download_segmented(&client, url, destination, segment_count, total_size, &downloaded).await
+        } else {
+            Self::download_single(&client, url, destination, &downloaded).await
+        };
+
+        sampler.abort();
+        result?;
 
-            let progress = if total_size > 0 {
-                (downloaded as f32 / total_size as f32) * 100.0
+        progress_callback(DownloadProgress {
+            downloaded_bytes: downloaded.load(Ordering::Relaxed),
+            total_bytes: total_size,
+            progress_percent: 100.0,
+            speed_mbps: 0.0,
+            eta_seconds: 0.0,
+        });
+
+        Ok(())
+    }
+
+    /// Split `total_size` across `segment_count` concurrent Range requests, each
+    /// writing to an independently resumable `.partN` file, then concatenate them.
+    async fn download_segmented(
+        client: &Client,
+        url: &str,
+        destination: &Path,
+        segment_count: usize,
+        total_size: u64,
+        downloaded: &Arc<AtomicU64>,
+    ) -> Result<(), Box<dyn Error>> {
+        let segments = Self::plan_segments(destination, segment_count, total_size);
+
+        let mut tasks = Vec::with_capacity(segments.len());
+        for segment in segments.iter().cloned() {
+            let client = client.clone();
+            let url = url.to_string();
+            let downloaded = Arc::clone(downloaded);
+            tasks.push(tokio::spawn(async move {
+                Self::download_segment(&client, &url, segment, downloaded).await
+            }));
+        }
+
+        for task in tasks {
+            task.await??;
+        }
+
+        Self::concatenate_segments(destination, &segments)?;
+
+        Ok(())
+    }
+
+    /// Work out byte ranges and part-file paths for each segment
+    ///
+    /// Clamped so `base_size` never rounds down to 0 for files smaller than
+    /// `segment_count` bytes, which would otherwise underflow `end = start + base_size - 1`.
+    fn plan_segments(destination: &Path, segment_count: usize, total_size: u64) -> Vec<Segment> {
+        let segment_count = (segment_count.max(1) as u64).min(total_size.max(1));
+        let base_size = total_size / segment_count;
+        let mut segments = Vec::new();
+        let mut start = 0u64;
+
+        for index in 0..segment_count {
+            let end = if index == segment_count - 1 {
+                total_size - 1
             } else {
-                0.0
+                start + base_size - 1
             };
 
-            progress_callback(DownloadProgress {
-                downloaded_bytes: downloaded,
-                total_bytes: total_size,
-                progress_percent: progress,
-                speed_mbps: 0.0, // TODO: Calculate speed
+            segments.push(Segment {
+                start,
+                end,
+                part_path: Self::part_path(destination, index as usize),
             });
+
+            start = end + 1;
+        }
+
+        segments
+    }
+
+    async fn download_segment(
+        client: &Client,
+        url: &str,
+        segment: Segment,
+        downloaded: Arc<AtomicU64>,
+    ) -> Result<(), Box<dyn Error>> {
+        let segment_size = segment.end - segment.start + 1;
+        let already_downloaded = segment
+            .part_path
+            .metadata()
+            .map(|m| m.len())
+            .unwrap_or(0)
+            .min(segment_size);
+
+        downloaded.fetch_add(already_downloaded, Ordering::Relaxed);
+
+        if already_downloaded == segment_size {
+            return Ok(());
+        }
+
+        let range_start = segment.start + already_downloaded;
+        let expected_len = segment.end - range_start + 1;
+        let response = client
+            .get(url)
+            .header("Range", format!("bytes={}-{}", range_start, segment.end))
+            .send()
+            .await?;
+
+        // A server that ignores the Range header (or returns an error page) would
+        // otherwise have its body appended straight into the part file and silently
+        // corrupt the concatenated output, so only a confirmed partial response is
+        // trusted here.
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            return Err(format!(
+                "Expected 206 Partial Content for bytes={}-{}, got {}",
+                range_start, segment.end, response.status()
+            )
+            .into());
+        }
+
+        if let Some(content_length) = response.content_length() {
+            if content_length != expected_len {
+                return Err(format!(
+                    "Segment response length mismatch: expected {} bytes, server declared {}",
+                    expected_len, content_length
+                )
+                .into());
+            }
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&segment.part_path)?;
+
+        let mut written = 0u64;
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk)?;
+            written += chunk.len() as u64;
+            downloaded.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        }
+
+        if written != expected_len {
+            return Err(format!(
+                "Segment body shorter than expected: wanted {} bytes, got {}",
+                expected_len, written
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Merge completed segment part-files into the destination, in order, then clean up
+    fn concatenate_segments(destination: &Path, segments: &[Segment]) -> Result<(), Box<dyn Error>> {
+        let mut output = File::create(destination)?;
+
+        for segment in segments {
+            let mut part = File::open(&segment.part_path)?;
+            std::io::copy(&mut part, &mut output)?;
+        }
+
+        for segment in segments {
+            let _ = std::fs::remove_file(&segment.part_path);
         }
 
         Ok(())
     }
 
-    /// Resume download from partial file
-    pub async fn resume_download<F>(
+    /// Single-stream fallback for servers that don't support Range requests
+    async fn download_single(
+        client: &Client,
         url: &str,
         destination: &Path,
-        progress_callback: F,
-    ) -> Result<(), Box<dyn Error>>
-    where
-        F: Fn(DownloadProgress) + Send + 'static,
-    {
+        downloaded: &Arc<AtomicU64>,
+    ) -> Result<(), Box<dyn Error>> {
         let existing_size = if destination.exists() {
             std::fs::metadata(destination)?.len()
         } else {
             0
         };
 
-        let client = Client::new();
         let response = client
             .get(url)
             .header("Range", format!("bytes={}-", existing_size))
             .send()
             .await?;
 
-        let total_size = response.content_length().unwrap_or(0) + existing_size;
-        let mut downloaded = existing_size;
-        let mut stream = response.bytes_stream();
-        let mut file = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(destination)?;
+        // The server may ignore the Range header and reply with the full body even
+        // when asked to resume. Only trust the on-disk bytes when it actually
+        // confirms a partial response; otherwise start over to avoid appending a
+        // second copy of the file onto the existing one.
+        let mut file = if response.status() == StatusCode::PARTIAL_CONTENT {
+            downloaded.fetch_add(existing_size, Ordering::Relaxed);
+            let mut file = OpenOptions::new().create(true).append(true).open(destination)?;
+            file.seek(SeekFrom::End(0))?;
+            file
+        } else {
+            File::create(destination)?
+        };
 
+        let mut stream = response.bytes_stream();
         while let Some(chunk) = stream.next().await {
             let chunk = chunk?;
             file.write_all(&chunk)?;
-            downloaded += chunk.len() as u64;
-
-            let progress = if total_size > 0 {
-                (downloaded as f32 / total_size as f32) * 100.0
-            } else {
-                0.0
-            };
-
-            progress_callback(DownloadProgress {
-                downloaded_bytes: downloaded,
-                total_bytes: total_size,
-                progress_percent: progress,
-                speed_mbps: 0.0,
-            });
+            downloaded.fetch_add(chunk.len() as u64, Ordering::Relaxed);
         }
 
         Ok(())
     }
+
+    fn part_path(destination: &Path, index: usize) -> PathBuf {
+        let mut part = destination.as_os_str().to_owned();
+        part.push(format!(".part{}", index));
+        PathBuf::from(part)
+    }
+
+    async fn fetch_total_size(client: &Client, url: &str) -> Result<u64, Box<dyn Error>> {
+        let response = client.head(url).send().await?;
+        Ok(response.content_length().unwrap_or(0))
+    }
+
+    /// Probe whether the server honors `Range` requests by requesting the first byte
+    async fn supports_range(client: &Client, url: &str) -> Result<bool, Box<dyn Error>> {
+        let response = client
+            .get(url)
+            .header("Range", "bytes=0-0")
+            .send()
+            .await?;
+
+        Ok(response.status() == StatusCode::PARTIAL_CONTENT
+            || response
+                .headers()
+                .get("Accept-Ranges")
+                .map(|v| v == "bytes")
+                .unwrap_or(false))
+    }
+
+    /// Periodically samples `downloaded` and emits a `DownloadProgress` with speed
+    /// smoothed via an exponential moving average and a derived ETA.
+    fn spawn_sampler<F>(
+        downloaded: Arc<AtomicU64>,
+        total_size: u64,
+        progress_callback: Arc<F>,
+    ) -> tokio::task::JoinHandle<()>
+    where
+        F: Fn(DownloadProgress) + Send + Sync + 'static,
+    {
+        tokio::spawn(async move {
+            let mut last_bytes = downloaded.load(Ordering::Relaxed);
+            let mut speed_mbps = 0.0f32;
+
+            loop {
+                tokio::time::sleep(SAMPLE_INTERVAL).await;
+
+                let current_bytes = downloaded.load(Ordering::Relaxed);
+                let delta_bytes = current_bytes.saturating_sub(last_bytes);
+                last_bytes = current_bytes;
+
+                let delta_secs = SAMPLE_INTERVAL.as_secs_f32();
+                let instant_mbps = (delta_bytes as f32 / delta_secs) / 1_000_000.0;
+                speed_mbps = SPEED_EMA_ALPHA * instant_mbps + (1.0 - SPEED_EMA_ALPHA) * speed_mbps;
+
+                let progress_percent = if total_size > 0 {
+                    (current_bytes as f32 / total_size as f32) * 100.0
+                } else {
+                    0.0
+                };
+
+                let eta_seconds = if speed_mbps > 0.0 && total_size > current_bytes {
+                    ((total_size - current_bytes) as f32 / 1_000_000.0) / speed_mbps
+                } else {
+                    0.0
+                };
+
+                progress_callback(DownloadProgress {
+                    downloaded_bytes: current_bytes,
+                    total_bytes: total_size,
+                    progress_percent,
+                    speed_mbps,
+                    eta_seconds,
+                });
+            }
+        })
+    }
 }
@@ -1,96 +1,200 @@
-use reqwest::Client;
 use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
-use std::process::Command;
-use crate::types::{GameVersion, DownloadProgress};
-use crate::services::download_manager::DownloadManager;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use crate::types::{GameVersion, ExtractProgress, LauncherStatus, WineConfig};
+use crate::services::download_manager::{DownloadManager, DEFAULT_SEGMENT_COUNT};
+use crate::services::compatibility_runner::CompatibilityRunner;
+use crate::services::release_provider::{GitHubReleaseProvider, ReleaseProvider};
 
 pub struct GameService;
 
 impl GameService {
-    const API_URL: &'static str = "http://localhost:8000/api";
-
-    /// Check latest game version from server
-    pub async fn check_version() -> Result<GameVersion, Box<dyn Error>> {
-        let client = Client::new();
-        
-        let response = client
-            .get(format!("{}/game/version", Self::API_URL))
-            .send()
-            .await?;
-
-        if response.status().is_success() {
-            let version: GameVersion = response.json().await?;
-            Ok(version)
-        } else {
-            Err("Failed to fetch game version".into())
-        }
+    /// Check latest game version from the GitHub Releases API for a configured `owner/repo`
+    pub async fn check_version(owner: &str, repo: &str) -> Result<GameVersion, Box<dyn Error>> {
+        Self::check_version_with(owner, repo, false).await
     }
 
-    /// Download game with progress callback
-    pub async fn download_game<F>(
+    /// Check latest game version, optionally including prereleases
+    pub async fn check_version_with(
+        owner: &str,
+        repo: &str,
+        include_prereleases: bool,
+    ) -> Result<GameVersion, Box<dyn Error>> {
+        let provider = GitHubReleaseProvider::new(owner, repo);
+        provider.latest_version(include_prereleases).await
+    }
+
+    /// Download and extract the game from `download_url` (the GitHub release asset
+    /// URL resolved by `check_version`), reporting progress through the unified
+    /// `LauncherStatus` channel as it moves through the "Downloading" and
+    /// "Extracting" stages.
+    pub async fn download_game(
         version: String,
+        download_url: String,
         install_path: String,
-        progress_callback: F,
-    ) -> Result<(), Box<dyn Error>>
-    where
-        F: Fn(DownloadProgress) + Send + 'static,
-    {
-        let download_url = format!("{}/game/download?version={}", Self::API_URL, version);
+        status_callback: impl Fn(LauncherStatus) + Send + Sync + 'static,
+    ) -> Result<(), Box<dyn Error>> {
         let install_path = PathBuf::from(install_path);
-        
+        let status_callback = Arc::new(status_callback);
+
         // Ensure install directory exists
         std::fs::create_dir_all(&install_path)?;
-        
-        // Download game files
+
+        status_callback(LauncherStatus::stage("Downloading", None));
+        let callback = Arc::clone(&status_callback);
         DownloadManager::download_file(
             &download_url,
             &install_path.join("game.zip"),
-            progress_callback,
+            DEFAULT_SEGMENT_COUNT,
+            move |progress| callback(LauncherStatus::from_download("Downloading", &progress)),
         )
         .await?;
+        status_callback(LauncherStatus::done("Downloading"));
 
-        // Extract downloaded archive
-        Self::extract_game(&install_path)?;
+        status_callback(LauncherStatus::stage("Extracting", None));
+        let callback = Arc::clone(&status_callback);
+        Self::extract_game(&install_path.join("game.zip"), &install_path, move |progress| {
+            callback(LauncherStatus::from_extract(&progress))
+        })?;
+        status_callback(LauncherStatus::done("Extracting"));
+
+        std::fs::write(install_path.join("version.txt"), &version)?;
 
         Ok(())
     }
 
-    /// Extract game archive
-    fn extract_game(install_path: &PathBuf) -> Result<(), Box<dyn Error>> {
-        // TODO: Implement zip extraction
-        // For now, assume game is already extracted
-        println!("Game extraction completed at: {:?}", install_path);
+    /// Extract a zip archive into `install_path`, reporting per-file progress
+    fn extract_game<F>(
+        archive_path: &PathBuf,
+        install_path: &PathBuf,
+        progress_callback: F,
+    ) -> Result<(), Box<dyn Error>>
+    where
+        F: Fn(ExtractProgress),
+    {
+        let archive_file = File::open(archive_path)?;
+        let mut archive = zip::ZipArchive::new(archive_file)?;
+        let total_files = archive.len() as u32;
+
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            let entry_name = entry.name().to_string();
+
+            let Some(entry_path) = entry.enclosed_name() else {
+                continue;
+            };
+            let out_path = install_path.join(entry_path);
+
+            if entry.is_dir() {
+                std::fs::create_dir_all(&out_path)?;
+            } else {
+                if let Some(parent) = out_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let mut out_file = File::create(&out_path)?;
+                std::io::copy(&mut entry, &mut out_file)?;
+            }
+
+            progress_callback(ExtractProgress {
+                current_file: entry_name,
+                files_extracted: i as u32 + 1,
+                total_files,
+            });
+        }
+
         Ok(())
     }
 
     /// Launch game with authentication token
+    ///
+    /// On Windows the executable is spawned directly. On other platforms it is run
+    /// through a configured Wine/Proton prefix, which is created and optionally
+    /// equipped with DXVK on first launch. The child's stdout/stderr are streamed
+    /// back as `log_line` events so crashes are visible in the launcher.
     pub async fn launch_game(
         token: String,
         install_path: String,
+        wine_config: WineConfig,
+        status_callback: impl Fn(LauncherStatus) + Send + Sync + 'static,
     ) -> Result<(), Box<dyn Error>> {
         let game_exe = PathBuf::from(install_path).join("RemakeSoF.exe");
-        
+
         if !game_exe.exists() {
             return Err(format!("Game executable not found at: {:?}", game_exe).into());
         }
 
-        // Launch game with token as command line argument
-        Command::new(game_exe)
-            .arg("--token")
-            .arg(token)
-            .spawn()?;
+        let status_callback = Arc::new(status_callback);
+        status_callback(LauncherStatus::stage("Launching", None));
+
+        let child = if cfg!(windows) {
+            Command::new(game_exe)
+                .arg("--token")
+                .arg(token)
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()?
+        } else {
+            let callback = Arc::clone(&status_callback);
+            CompatibilityRunner::ensure_prefix(&wine_config, &move |step| {
+                callback(LauncherStatus::stage("Launching", Some(step.to_string())));
+            })?;
+
+            CompatibilityRunner::launch(&wine_config, &game_exe, &["--token".to_string(), token])?
+        };
+
+        Self::stream_child_logs(child, status_callback);
 
         Ok(())
     }
 
+    /// Spawn threads that forward the child process's stdout/stderr as `log_line`
+    /// events, then reap the child and emit a terminal status once it exits so it
+    /// never lingers as a zombie and its exit code is never silently dropped.
+    fn stream_child_logs(
+        mut child: std::process::Child,
+        status_callback: Arc<impl Fn(LauncherStatus) + Send + Sync + 'static>,
+    ) {
+        if let Some(stdout) = child.stdout.take() {
+            let callback = Arc::clone(&status_callback);
+            std::thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    callback(LauncherStatus::log("Launching", line));
+                }
+            });
+        }
+
+        if let Some(stderr) = child.stderr.take() {
+            let callback = Arc::clone(&status_callback);
+            std::thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    callback(LauncherStatus::log("Launching", line));
+                }
+            });
+        }
+
+        std::thread::spawn(move || match child.wait() {
+            Ok(status) if status.success() => {
+                status_callback(LauncherStatus::done("Launching"));
+            }
+            Ok(status) => {
+                status_callback(LauncherStatus::failed("Launching", format!("Game exited with {}", status)));
+            }
+            Err(e) => {
+                status_callback(LauncherStatus::failed("Launching", e.to_string()));
+            }
+        });
+    }
+
     /// Get default installation path
     pub fn get_install_path() -> Result<String, Box<dyn Error>> {
         let install_dir = dirs::data_local_dir()
             .ok_or("Failed to get app data directory")?
             .join("RemakeSoF")
             .join("Game");
-        
+
         Ok(install_dir.to_string_lossy().to_string())
     }
 
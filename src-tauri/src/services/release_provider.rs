@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::error::Error;
+use crate::types::GameVersion;
+
+/// Abstraction over where the launcher learns about new game versions
+#[async_trait]
+pub trait ReleaseProvider {
+    /// Fetch the latest applicable release as a `GameVersion`
+    async fn latest_version(&self, include_prereleases: bool) -> Result<GameVersion, Box<dyn Error>>;
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    published_at: String,
+    body: Option<String>,
+    draft: bool,
+    prerelease: bool,
+    assets: Vec<GitHubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubAsset {
+    name: String,
+    browser_download_url: String,
+    size: u64,
+}
+
+/// Sources game releases from the GitHub Releases API for a configured `owner/repo`
+pub struct GitHubReleaseProvider {
+    owner: String,
+    repo: String,
+}
+
+impl GitHubReleaseProvider {
+    pub fn new(owner: impl Into<String>, repo: impl Into<String>) -> Self {
+        Self {
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    /// Pick the asset that holds the installable game archive
+    fn select_asset(release: &GitHubRelease) -> Option<&GitHubAsset> {
+        release.assets.iter().find(|asset| asset.name.ends_with(".zip"))
+    }
+}
+
+#[async_trait]
+impl ReleaseProvider for GitHubReleaseProvider {
+    async fn latest_version(&self, include_prereleases: bool) -> Result<GameVersion, Box<dyn Error>> {
+        let client = Client::new();
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/releases",
+            self.owner, self.repo
+        );
+
+        let response = client
+            .get(&url)
+            .header("User-Agent", "gamelauncher")
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch GitHub releases ({})", response.status()).into());
+        }
+
+        let releases: Vec<GitHubRelease> = response.json().await?;
+
+        let release = releases
+            .into_iter()
+            .find(|release| !release.draft && (include_prereleases || !release.prerelease))
+            .ok_or("No matching release found")?;
+
+        let asset = Self::select_asset(&release).ok_or("Release has no game archive asset")?;
+
+        Ok(GameVersion {
+            version: release.tag_name.clone(),
+            release_date: release.published_at.clone(),
+            download_url: asset.browser_download_url.clone(),
+            file_size: asset.size,
+            checksum: String::new(),
+            changelog: release.body.clone().unwrap_or_default(),
+        })
+    }
+}
@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::path::Path;
+use std::sync::Arc;
+use crate::types::{DownloadProgress, ManifestEntry, RepairResult};
+use crate::services::download_manager::{DownloadManager, DEFAULT_SEGMENT_COUNT};
+use crate::services::file_manager::FileManager;
+
+/// Verifies an installation against a manifest and repairs files that are
+/// missing, the wrong size, or fail their checksum
+pub struct RepairManager;
+
+impl RepairManager {
+    /// Check every file in `manifest` against the installation on disk, without
+    /// downloading anything
+    pub fn verify_installation(install_path: &Path, manifest: &[ManifestEntry]) -> RepairResult {
+        let broken = Self::broken_entries(install_path, manifest);
+
+        RepairResult {
+            checked: manifest.len() as u32,
+            corrupt: broken.len() as u32,
+            repaired: 0,
+            broken_files: broken.into_iter().map(|entry| entry.relative_path).collect(),
+        }
+    }
+
+    /// Verify the installation, then re-download only the files that are broken.
+    /// `file_base_url` is the root the manifest's relative paths are fetched under,
+    /// e.g. `{server}/api/game/file` - distinct from the release asset URL used for
+    /// the initial install.
+    pub async fn repair_installation(
+        install_path: &Path,
+        manifest: &[ManifestEntry],
+        file_base_url: &str,
+        progress_callback: impl Fn(DownloadProgress) + Send + Sync + 'static,
+    ) -> Result<RepairResult, Box<dyn Error>> {
+        let broken = {
+            let install_path = install_path.to_path_buf();
+            let manifest = manifest.to_vec();
+            tokio::task::spawn_blocking(move || Self::broken_entries(&install_path, &manifest)).await?
+        };
+        let progress_callback = Arc::new(progress_callback);
+        let mut repaired = 0;
+        let mut broken_files = Vec::new();
+
+        for entry in &broken {
+            let url = format!("{}?path={}", file_base_url, entry.relative_path);
+            let destination = install_path.join(&entry.relative_path);
+            let callback = Arc::clone(&progress_callback);
+
+            // A failed download (wrong route, 404, network error, ...) must not abort
+            // the whole pass - the remaining broken files are still worth attempting,
+            // and this entry goes into `broken_files`. The download error itself is
+            // not kept.
+            let downloaded = DownloadManager::download_file(&url, &destination, DEFAULT_SEGMENT_COUNT, move |progress| {
+                callback(progress)
+            })
+            .await
+            .is_ok();
+
+            let intact = if downloaded {
+                let sha256 = entry.sha256.clone();
+                tokio::task::spawn_blocking(move || {
+                    FileManager::verify_file_integrity(&destination, &sha256).unwrap_or(false)
+                })
+                .await?
+            } else {
+                false
+            };
+
+            if intact {
+                repaired += 1;
+            } else {
+                broken_files.push(entry.relative_path.clone());
+            }
+        }
+
+        Ok(RepairResult {
+            checked: manifest.len() as u32,
+            corrupt: broken.len() as u32,
+            repaired,
+            broken_files,
+        })
+    }
+
+    /// Manifest entries whose on-disk file is missing, the wrong size, or hash-mismatched
+    fn broken_entries(install_path: &Path, manifest: &[ManifestEntry]) -> Vec<ManifestEntry> {
+        manifest
+            .iter()
+            .filter(|entry| !Self::is_intact(install_path, entry))
+            .cloned()
+            .collect()
+    }
+
+    fn is_intact(install_path: &Path, entry: &ManifestEntry) -> bool {
+        let file_path = install_path.join(&entry.relative_path);
+
+        if !file_path.exists() {
+            return false;
+        }
+
+        let size_matches = FileManager::get_file_size(&file_path)
+            .map(|size| size == entry.size)
+            .unwrap_or(false);
+
+        size_matches && FileManager::verify_file_integrity(&file_path, &entry.sha256).unwrap_or(false)
+    }
+}
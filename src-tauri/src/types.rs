@@ -24,6 +24,7 @@ pub struct GameVersion {
     pub download_url: String,
     pub file_size: u64,
     pub checksum: String,
+    pub changelog: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,4 +33,145 @@ pub struct DownloadProgress {
     pub total_bytes: u64,
     pub progress_percent: f32,
     pub speed_mbps: f32,
+    pub eta_seconds: f32,
+}
+
+/// Wine/Proton compatibility settings used to launch the game on non-Windows hosts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WineConfig {
+    pub prefix_path: String,
+    pub wine_binary: String,
+    pub dxvk_enabled: bool,
+    /// Path (or PATH-resolvable name) of the DXVK install tool to run when `dxvk_enabled`
+    #[serde(default = "default_dxvk_tool")]
+    pub dxvk_tool: String,
+    pub extra_env: std::collections::HashMap<String, String>,
+}
+
+fn default_dxvk_tool() -> String {
+    "setup_dxvk".to_string()
+}
+
+impl Default for WineConfig {
+    fn default() -> Self {
+        Self {
+            prefix_path: String::new(),
+            wine_binary: "wine".to_string(),
+            dxvk_enabled: false,
+            dxvk_tool: default_dxvk_tool(),
+            extra_env: std::collections::HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractProgress {
+    pub current_file: String,
+    pub files_extracted: u32,
+    pub total_files: u32,
+}
+
+/// A single expected file in an installation manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub relative_path: String,
+    pub sha256: String,
+    pub size: u64,
+}
+
+/// Outcome of a verify or repair pass against an installation manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairResult {
+    pub checked: u32,
+    pub corrupt: u32,
+    pub repaired: u32,
+    /// Relative paths still broken after this pass (all corrupt entries for a plain
+    /// verify; only the ones repair couldn't fix for a repair pass)
+    pub broken_files: Vec<String>,
+}
+
+/// Unified progress/log payload emitted on the single `launcher-status` channel so the
+/// frontend can show a stage indicator (e.g. "Downloading" -> "Extracting" -> "Verifying"
+/// -> "Launching") alongside a live log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherStatus {
+    pub stage: String,
+    pub label: Option<String>,
+    pub progress: Option<f32>,
+    pub complete: bool,
+    pub log_line: Option<String>,
+    pub error: Option<String>,
+}
+
+impl LauncherStatus {
+    /// A bare stage change, optionally with a human-readable label
+    pub fn stage(stage: &str, label: Option<String>) -> Self {
+        Self {
+            stage: stage.to_string(),
+            label,
+            progress: None,
+            complete: false,
+            log_line: None,
+            error: None,
+        }
+    }
+
+    /// A single log line attributed to `stage`
+    pub fn log(stage: &str, log_line: String) -> Self {
+        Self {
+            stage: stage.to_string(),
+            label: None,
+            progress: None,
+            complete: false,
+            log_line: Some(log_line),
+            error: None,
+        }
+    }
+
+    /// Marks `stage` as finished successfully
+    pub fn done(stage: &str) -> Self {
+        Self {
+            stage: stage.to_string(),
+            label: None,
+            progress: Some(100.0),
+            complete: true,
+            log_line: None,
+            error: None,
+        }
+    }
+
+    /// A terminal error for `stage`
+    pub fn failed(stage: &str, error: String) -> Self {
+        Self {
+            stage: stage.to_string(),
+            label: None,
+            progress: None,
+            complete: true,
+            log_line: None,
+            error: Some(error),
+        }
+    }
+
+    pub fn from_download(stage: &str, progress: &DownloadProgress) -> Self {
+        Self {
+            stage: stage.to_string(),
+            label: Some(format!("{:.1} MB/s, ETA {:.0}s", progress.speed_mbps, progress.eta_seconds)),
+            progress: Some(progress.progress_percent),
+            complete: false,
+            log_line: None,
+            error: None,
+        }
+    }
+
+    pub fn from_extract(progress: &ExtractProgress) -> Self {
+        let percent = (progress.files_extracted as f32 / progress.total_files.max(1) as f32) * 100.0;
+        Self {
+            stage: "Extracting".to_string(),
+            label: Some(progress.current_file.clone()),
+            progress: Some(percent),
+            complete: false,
+            log_line: None,
+            error: None,
+        }
+    }
 }